@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::asm::statements::{parse_statement, Directive, GenericDirective, Statement};
+
+/// Resolves `.include` directives by recursively parsing the referenced
+/// files and splicing their statements in place of the directive, so
+/// downstream passes (function extraction, `is_section_start`, ...) see one
+/// flattened stream instead of an opaque `Directive::Generic`.
+///
+/// `.incbin` embeds raw binary data rather than assembly text, so it can't
+/// be parsed into `Statement`s; it's intentionally left as an opaque
+/// `Directive::Generic`, unresolved.
+#[derive(Default)]
+pub struct IncludeResolver {
+    /// Already-read file contents, keyed by canonicalized path. `None`
+    /// records a file that was referenced but couldn't be read, so it's
+    /// skipped rather than re-attempted or propagated as a hard error.
+    /// Owns every buffer that the `Statement`s returned from `parse_file`
+    /// borrow from.
+    sources: HashMap<PathBuf, Option<String>>,
+}
+
+impl IncludeResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses `path` and every file it (transitively) `.include`s, returning
+    /// the merged, flattened statement stream.
+    pub fn parse_file<'a>(&'a mut self, path: &Path) -> io::Result<Vec<Statement<'a>>> {
+        let contents = fs::read_to_string(path)?;
+        let canon = canonicalize(path);
+        self.sources.insert(canon.clone(), Some(contents));
+        self.read_includes(&canon);
+
+        let mut visiting = Vec::new();
+        Ok(self.parse_recursive(&canon, &mut visiting))
+    }
+
+    /// Worklist pass: parses `path` (already in `self.sources`) and every
+    /// `.include`d file transitively reachable from it, reading each into
+    /// `self.sources` before `parse_recursive` needs it. Checks the map
+    /// before pushing a path onto the pending stack so include cycles
+    /// terminate, and uses the exact same include-detection and
+    /// path-resolution logic as `parse_recursive` so the two passes can't
+    /// disagree on what a directive resolves to.
+    fn read_includes(&mut self, path: &Path) {
+        let mut pending = vec![path.to_path_buf()];
+        while let Some(p) = pending.pop() {
+            let Some(Some(contents)) = self.sources.get(&p) else {
+                continue;
+            };
+            let dir = p.parent().unwrap_or_else(|| Path::new(".")).to_path_buf();
+
+            let mut includes = Vec::new();
+            let mut rest = contents.as_str();
+            while !rest.is_empty() {
+                let Ok((tail, stmt)) = parse_statement(rest) else {
+                    break;
+                };
+                rest = tail;
+                if let Some(inc_path) = generic_include_path(&stmt, &dir) {
+                    includes.push(inc_path);
+                }
+            }
+
+            for inc_path in includes {
+                if self.sources.contains_key(&inc_path) {
+                    continue;
+                }
+                let contents = fs::read_to_string(&inc_path).ok();
+                let readable = contents.is_some();
+                self.sources.insert(inc_path.clone(), contents);
+                if readable {
+                    pending.push(inc_path);
+                }
+            }
+        }
+    }
+
+    /// Parses `path` (already present in `self.sources`) into statements,
+    /// splicing in the flattened statements of every `.include` it contains
+    /// in place of the directive itself. A path with no readable contents
+    /// (missing or unreadable file), or one already being visited (an include
+    /// cycle), silently contributes no statements.
+    fn parse_recursive<'a>(
+        &'a self,
+        path: &Path,
+        visiting: &mut Vec<PathBuf>,
+    ) -> Vec<Statement<'a>> {
+        let Some(Some(contents)) = self.sources.get(path) else {
+            return Vec::new();
+        };
+        if visiting.contains(&path.to_path_buf()) {
+            // Include cycle: stop recursing rather than looping forever.
+            return Vec::new();
+        }
+        visiting.push(path.to_path_buf());
+
+        let dir = path.parent().unwrap_or_else(|| Path::new(".")).to_path_buf();
+        let mut out = Vec::new();
+        let mut rest = contents.as_str();
+        while !rest.is_empty() {
+            let Ok((tail, stmt)) = parse_statement(rest) else {
+                break;
+            };
+            rest = tail;
+
+            if let Some(inc_path) = generic_include_path(&stmt, &dir) {
+                out.extend(self.parse_recursive(&inc_path, visiting));
+                continue;
+            }
+            out.push(stmt);
+        }
+
+        visiting.pop();
+        out
+    }
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// If `stmt` is a `.include` directive, resolves and canonicalizes the path
+/// it names relative to `dir` (the including file's directory).
+fn generic_include_path(stmt: &Statement<'_>, dir: &Path) -> Option<PathBuf> {
+    let Statement::Directive(Directive::Generic(GenericDirective(body))) = stmt else {
+        return None;
+    };
+    parse_include_path(body).map(|included| canonicalize(&dir.join(included)))
+}
+
+/// Extracts the quoted path out of a generic directive body like
+/// `include "other.s"`, returning `None` for any other directive.
+fn parse_include_path(body: &str) -> Option<&str> {
+    let rest = body.strip_prefix("include")?.trim_start();
+    let rest = rest.strip_prefix('"')?;
+    let end = rest.find('"')?;
+    Some(&rest[..end])
+}
+
+#[test]
+fn test_parse_include_path() {
+    assert_eq!(parse_include_path(r#"include "foo.s""#), Some("foo.s"));
+    assert_eq!(
+        parse_include_path(r#"include "sub/foo.s""#),
+        Some("sub/foo.s")
+    );
+    assert_eq!(parse_include_path("globl\tmain"), None);
+    assert_eq!(parse_include_path(r#"incbin "data.bin""#), None);
+}
+
+#[test]
+fn test_resolver_splices_included_statements() {
+    let dir = std::env::temp_dir().join(format!(
+        "cargo-show-asm-include-test-{:?}",
+        std::thread::current().id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    let main_path = dir.join("main.s");
+    let inc_path = dir.join("inc.s");
+
+    fs::write(&inc_path, "\tnop\n").unwrap();
+    fs::write(
+        &main_path,
+        "\tpushq\t%rbp\n\t.include \"inc.s\"\n\tpopq\t%rbp\n",
+    )
+    .unwrap();
+
+    let mut resolver = IncludeResolver::new();
+    let statements = resolver.parse_file(&main_path).unwrap();
+
+    let ops: Vec<&str> = statements
+        .iter()
+        .filter_map(|s| match s {
+            Statement::Instruction(i) => Some(i.op),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(ops, vec!["pushq", "nop", "popq"]);
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_resolver_skips_missing_include_instead_of_failing() {
+    let dir = std::env::temp_dir().join(format!(
+        "cargo-show-asm-include-missing-test-{:?}",
+        std::thread::current().id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    let main_path = dir.join("main.s");
+
+    fs::write(
+        &main_path,
+        "\tpushq\t%rbp\n\t.include \"does_not_exist.s\"\n\tpopq\t%rbp\n",
+    )
+    .unwrap();
+
+    let mut resolver = IncludeResolver::new();
+    let statements = resolver.parse_file(&main_path).unwrap();
+
+    let ops: Vec<&str> = statements
+        .iter()
+        .filter_map(|s| match s {
+            Statement::Instruction(i) => Some(i.op),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(ops, vec!["pushq", "popq"]);
+
+    fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,6 @@
+pub mod include;
+pub mod operand;
+pub mod source;
+pub mod statements;
+
+pub use statements::{Directive, FilePath, Instruction, Label, Loc, Statement};
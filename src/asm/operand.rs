@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+
+use crate::asm::statements::Statement;
+
+/// A single tokenized instruction operand.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Operand<'a> {
+    /// e.g. `%rax`, `%w0`
+    Register(&'a str),
+    /// e.g. `$0x10`, `#imm`
+    Immediate(&'a str),
+    /// e.g. `-8(%rbp)`, `[x0]`
+    Memory(&'a str),
+    /// Names a `Label` that may resolve elsewhere in the same function,
+    /// e.g. `LBB0_1`, `.Lexception0`.
+    Label(&'a str),
+    /// Anything else we don't classify further.
+    Other(&'a str),
+}
+
+/// Splits an `Instruction::args` string into its operands and classifies
+/// each one.
+pub fn tokenize_operands(args: &str) -> Vec<Operand<'_>> {
+    split_top_level(args)
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .map(classify)
+        .collect()
+}
+
+/// Splits `args` on commas, ignoring commas nested inside `(...)`/`[...]` so
+/// a single memory operand like AT&T's `-4(%rbp,%rax,4)` or ARM's
+/// `[x1, #8]` isn't torn into multiple bogus operands.
+fn split_top_level(args: &str) -> impl Iterator<Item = &str> {
+    let mut tokens = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = (depth - 1).max(0),
+            ',' if depth == 0 => {
+                tokens.push(&args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    tokens.push(&args[start..]);
+    tokens.into_iter()
+}
+
+fn classify(token: &str) -> Operand<'_> {
+    if token.starts_with('%') {
+        Operand::Register(token)
+    } else if token.starts_with('$') || token.starts_with('#') {
+        Operand::Immediate(token)
+    } else if token.contains('(') || token.contains('[') {
+        Operand::Memory(token)
+    } else if is_label_like(token) {
+        Operand::Label(token)
+    } else {
+        Operand::Other(token)
+    }
+}
+
+/// Same alphanum-plus-`.`/`_` rule `parse_regular` uses for instruction
+/// names (ARM's `b.ne`, Wasm's `end_function`), applied here to operand
+/// tokens that might name a label instead of a register or immediate.
+fn is_label_like(token: &str) -> bool {
+    !token.is_empty()
+        && !token.starts_with(|c: char| c.is_ascii_digit())
+        && token
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '.' | '_'))
+}
+
+/// Whether `op` is a branch/jump mnemonic that can target a label, as
+/// opposed to an instruction that merely names one (e.g. ARM's
+/// `adr x0, .Lfoo` or x86's `leaq .Lfoo(%rip), %rax`). Covers x86 (`jmp`,
+/// `je`, `call`...), ARM (`b`, `b.ne`, `bl`, `cbz`, `tbnz`...), and Wasm
+/// (`br`, `br_if`) naming.
+fn is_branch_or_jump(op: &str) -> bool {
+    // Strip the ARM condition suffix, e.g. `b.ne` -> `b`.
+    let base = op.split('.').next().unwrap_or(op);
+    matches!(base, "call" | "callq" | "b" | "bl" | "blr" | "br")
+        || base.starts_with('j')
+        || base.starts_with("br_")
+        || base.starts_with("cbz")
+        || base.starts_with("cbnz")
+        || base.starts_with("tbz")
+        || base.starts_with("tbnz")
+}
+
+/// Per-function map from branch/jump instructions to the label they target,
+/// built by tokenizing every instruction's operands and matching `Label`
+/// tokens against labels declared in the same statement slice.
+#[derive(Clone, Debug, Default)]
+pub struct ControlFlowMap<'a> {
+    /// `(branch instruction index, target label id)` pairs, in statement
+    /// order.
+    edges: Vec<(usize, &'a str)>,
+    /// Index of each label's statement, keyed by label id.
+    labels: HashMap<&'a str, usize>,
+}
+
+impl<'a> ControlFlowMap<'a> {
+    /// Builds the control-flow map for a single function's statements
+    /// (e.g. the slice between a symbol label and its `Lfunc_end`).
+    pub fn build(statements: &[Statement<'a>]) -> Self {
+        let mut labels = HashMap::new();
+        for (idx, stmt) in statements.iter().enumerate() {
+            if let Statement::Label(label) = stmt {
+                labels.insert(label.id, idx);
+            }
+        }
+
+        let mut edges = Vec::new();
+        for (idx, stmt) in statements.iter().enumerate() {
+            let Statement::Instruction(instr) = stmt else {
+                continue;
+            };
+            if !is_branch_or_jump(instr.op) {
+                continue;
+            }
+            let Some(args) = instr.args else {
+                continue;
+            };
+            for operand in tokenize_operands(args) {
+                if let Operand::Label(target) = operand {
+                    if labels.contains_key(target) {
+                        edges.push((idx, target));
+                    }
+                }
+            }
+        }
+
+        Self { edges, labels }
+    }
+
+    /// Lists every branch/jump edge within the function.
+    pub fn edges(&self) -> &[(usize, &'a str)] {
+        &self.edges
+    }
+
+    /// Follows the edge starting at instruction `from`, returning the
+    /// statement index of the label it targets, if any.
+    pub fn follow(&self, from: usize) -> Option<usize> {
+        self.edges
+            .iter()
+            .find(|(idx, _)| *idx == from)
+            .and_then(|(_, label)| self.labels.get(label).copied())
+    }
+}
+
+#[test]
+fn test_classify_operands() {
+    assert_eq!(
+        tokenize_operands("%rax, $0x10, -8(%rbp), LBB0_1"),
+        vec![
+            Operand::Register("%rax"),
+            Operand::Immediate("$0x10"),
+            Operand::Memory("-8(%rbp)"),
+            Operand::Label("LBB0_1"),
+        ]
+    );
+    assert_eq!(tokenize_operands("#imm"), vec![Operand::Immediate("#imm")]);
+    assert_eq!(tokenize_operands("[x0]"), vec![Operand::Memory("[x0]")]);
+}
+
+#[test]
+fn test_tokenize_operands_keeps_commas_inside_memory_operands_together() {
+    assert_eq!(
+        tokenize_operands("-4(%rbp,%rax,4), %edx"),
+        vec![
+            Operand::Memory("-4(%rbp,%rax,4)"),
+            Operand::Register("%edx"),
+        ]
+    );
+    assert_eq!(
+        tokenize_operands("x0, [x1, #8]"),
+        vec![Operand::Label("x0"), Operand::Memory("[x1, #8]")]
+    );
+}
+
+#[test]
+fn test_control_flow_map_follows_branch_to_label() {
+    use crate::asm::statements::{Instruction, Label};
+    use crate::demangle::LabelKind;
+
+    let statements = vec![
+        Statement::Instruction(Instruction {
+            op: "jmp",
+            args: Some("LBB0_1"),
+        }),
+        Statement::Label(Label {
+            id: "LBB0_1",
+            kind: LabelKind::Local,
+        }),
+        Statement::Instruction(Instruction {
+            op: "retq",
+            args: None,
+        }),
+    ];
+
+    let cfg = ControlFlowMap::build(&statements);
+    assert_eq!(cfg.edges(), &[(0, "LBB0_1")]);
+    assert_eq!(cfg.follow(0), Some(1));
+    assert_eq!(cfg.follow(2), None);
+}
+
+#[test]
+fn test_control_flow_map_ignores_non_branch_label_references() {
+    use crate::asm::statements::{Instruction, Label};
+    use crate::demangle::LabelKind;
+
+    // `adr`/`leaq` merely load a label's address; they don't branch to it.
+    let statements = vec![
+        Statement::Instruction(Instruction {
+            op: "adr",
+            args: Some("x0, .Lfoo"),
+        }),
+        Statement::Instruction(Instruction {
+            op: "leaq",
+            args: Some(".Lfoo(%rip), %rax"),
+        }),
+        Statement::Label(Label {
+            id: ".Lfoo",
+            kind: LabelKind::Local,
+        }),
+    ];
+
+    let cfg = ControlFlowMap::build(&statements);
+    assert!(cfg.edges().is_empty());
+}
@@ -0,0 +1,133 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::asm::statements::{Directive, FilePath, Loc, Statement};
+
+/// Caches source files as line vectors so a `.loc` directive that repeats
+/// the same file doesn't cause a re-read from disk.
+#[derive(Default)]
+struct SourceCache {
+    lines: HashMap<PathBuf, Option<Vec<String>>>,
+}
+
+impl SourceCache {
+    /// Returns the 1-indexed `line` of `path`, or `None` if the file is
+    /// missing, unreadable, or shorter than `line`.
+    fn line(&mut self, path: &Path, line: u64) -> Option<&str> {
+        let cached = self.lines.entry(path.to_path_buf()).or_insert_with(|| {
+            fs::read_to_string(path)
+                .ok()
+                .map(|contents| contents.lines().map(str::to_owned).collect())
+        });
+        cached
+            .as_ref()?
+            .get(usize::try_from(line.checked_sub(1)?).ok()?)
+            .map(String::as_str)
+    }
+}
+
+/// Writes `statements` to `out`, interleaving the original source line
+/// referenced by each `.loc` directive directly above the instructions it
+/// applies to (godbolt-style).
+///
+/// `.file` directives are collected into a file-index map as they're seen,
+/// the "current" `.loc` is tracked so consecutive directives pointing at the
+/// same file/line (per `Loc`'s `PartialEq`) don't repeat the source line,
+/// and files that can't be read are skipped, leaving just the assembly.
+pub fn interleave_source<'a, W: Write>(
+    statements: impl IntoIterator<Item = &'a Statement<'a>>,
+    out: &mut W,
+) -> io::Result<()> {
+    let mut files: BTreeMap<u64, FilePath<'a>> = BTreeMap::new();
+    let mut cache = SourceCache::default();
+    let mut current: Option<Loc<'a>> = None;
+
+    for stmt in statements {
+        match stmt {
+            Statement::Directive(Directive::File(file)) => {
+                files.insert(file.index, file.path);
+            }
+            Statement::Directive(Directive::Loc(loc)) if current != Some(*loc) => {
+                current = Some(*loc);
+                if let Some(path) = files.get(&loc.file) {
+                    if let Some(line) = cache.line(&path.as_full_path(), loc.line) {
+                        writeln!(out, "\t; {line}")?;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        writeln!(out, "{stmt}")?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_interleave_source_dedupes_and_skips_missing() {
+    use crate::asm::statements::{File, FilePath, Instruction};
+
+    let missing = Statement::Directive(Directive::File(File {
+        index: 1,
+        path: FilePath::FullPath("/does/not/exist.rs"),
+        md5: None,
+    }));
+    let loc_a = Statement::Directive(Directive::Loc(Loc {
+        file: 1,
+        line: 10,
+        column: 1,
+        extra: None,
+    }));
+    let instr = Statement::Instruction(Instruction {
+        op: "nop",
+        args: None,
+    });
+    // Same file+line as loc_a: must not re-emit the (missing) source line.
+    let loc_b = Statement::Directive(Directive::Loc(Loc {
+        file: 1,
+        line: 10,
+        column: 5,
+        extra: None,
+    }));
+
+    let statements = [
+        missing,
+        loc_a.clone(),
+        instr.clone(),
+        loc_b.clone(),
+        instr.clone(),
+    ];
+    let mut out = Vec::new();
+    interleave_source(&statements, &mut out).unwrap();
+    let rendered = String::from_utf8(out).unwrap();
+
+    // No "; " source-line comments were emitted since the file is unreadable.
+    assert!(!rendered.contains("; "));
+    assert_eq!(rendered.matches("nop").count(), 2);
+
+    // With a readable file, the repeated file+line emits its source line once.
+    let path = std::env::temp_dir().join(format!(
+        "cargo-show-asm-source-test-{:?}.rs",
+        std::thread::current().id()
+    ));
+    let source: String = (1..=10).map(|i| format!("line {i}\n")).collect();
+    fs::write(&path, source).unwrap();
+    let present = Statement::Directive(Directive::File(File {
+        index: 1,
+        path: FilePath::FullPath(path.to_str().unwrap()),
+        md5: None,
+    }));
+
+    let statements = [present, loc_a, instr.clone(), loc_b, instr];
+    let mut out = Vec::new();
+    interleave_source(&statements, &mut out).unwrap();
+    let rendered = String::from_utf8(out).unwrap();
+
+    assert_eq!(rendered.matches("\t; line 10").count(), 1);
+    assert_eq!(rendered.matches("nop").count(), 2);
+
+    fs::remove_file(&path).ok();
+}
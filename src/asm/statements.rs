@@ -499,6 +499,101 @@ pub fn parse_statement(input: &str) -> IResult<&str, Statement> {
     ))(input)
 }
 
+/// A byte range within the buffer originally passed to
+/// [`parse_statement_spanned`], with `start..end` covering the line that
+/// produced a `Statement`, including its trailing newline (`parse_statement`
+/// consumes it via `terminated(..., newline)`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Like [`parse_statement`], but also returns the [`Span`] of `input` that
+/// was consumed, measured as an offset into `base`.
+///
+/// `input` must be a suffix of `base` (as produced by repeatedly feeding the
+/// previous call's leftover back in) so the offsets are computed by pointer
+/// arithmetic rather than re-scanning the buffer. This lets callers report
+/// exact locations when the `Dunno` fallback fires, or build a line-number
+/// sidebar via [`LineIndex`], without keeping a running byte counter
+/// alongside every call site.
+pub fn parse_statement_spanned<'a>(
+    base: &'a str,
+    input: &'a str,
+) -> IResult<&'a str, (Span, Statement<'a>)> {
+    let start = input.as_ptr() as usize - base.as_ptr() as usize;
+    let (tail, stmt) = parse_statement(input)?;
+    let end = tail.as_ptr() as usize - base.as_ptr() as usize;
+    Ok((tail, (Span { start, end }, stmt)))
+}
+
+/// Converts byte offsets into a source buffer into 1-based `(line, column)`
+/// pairs, built once from the buffer's newline positions.
+#[derive(Clone, Debug)]
+pub struct LineIndex {
+    /// Byte offset of the start of each line, in order.
+    line_starts: Vec<usize>,
+    /// Length of the indexed buffer, so out-of-range offsets can be caught.
+    len: usize,
+}
+
+impl LineIndex {
+    pub fn new(input: &str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(input.match_indices('\n').map(|(i, _)| i + 1));
+        Self {
+            line_starts,
+            len: input.len(),
+        }
+    }
+
+    /// Converts a byte `offset` into a 1-based `(line, column)`.
+    ///
+    /// Panics if `offset` is beyond the end of the indexed buffer.
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        assert!(
+            offset <= self.len,
+            "offset {offset} is beyond the end of the indexed buffer (len {})",
+            self.len
+        );
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        (line + 1, offset - self.line_starts[line] + 1)
+    }
+}
+
+#[test]
+fn test_parse_statement_spanned() {
+    let base = "\tretq\n\tnop\n";
+    let (tail, (span, stmt)) = parse_statement_spanned(base, base).unwrap();
+    assert_eq!(span, Span { start: 0, end: 6 });
+    assert!(matches!(stmt, Statement::Instruction(i) if i.op == "retq"));
+
+    let (tail, (span, stmt)) = parse_statement_spanned(base, tail).unwrap();
+    assert!(tail.is_empty());
+    assert_eq!(span, Span { start: 6, end: 11 });
+    assert!(matches!(stmt, Statement::Instruction(i) if i.op == "nop"));
+}
+
+#[test]
+fn test_line_index() {
+    let index = LineIndex::new("abc\ndef\nghi");
+    assert_eq!(index.line_col(0), (1, 1));
+    assert_eq!(index.line_col(2), (1, 3));
+    assert_eq!(index.line_col(4), (2, 1));
+    assert_eq!(index.line_col(9), (3, 2));
+}
+
+#[test]
+#[should_panic(expected = "beyond the end of the indexed buffer")]
+fn test_line_index_panics_on_out_of_range_offset() {
+    let index = LineIndex::new("abc\ndef\nghi");
+    index.line_col(100);
+}
+
 fn good_for_label(c: char) -> bool {
     c == '.'
         || c == '$'
@@ -0,0 +1,108 @@
+//! Transparent decompression for `.s`, `.s.gz`, and `.s.zst` assembly
+//! inputs, so large compiler output can be pointed at directly without a
+//! manual `gunzip`/`zstd -d` step first.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Opens `path`, transparently decompressing it if it's gzip or zstd, and
+/// returns a reader yielding the decompressed bytes. Compression is
+/// detected from the file's magic bytes first, falling back to its
+/// `.gz`/`.zst` extension, so a misnamed file still decompresses correctly.
+///
+/// The returned reader yields the same line-oriented text `parse_statement`
+/// already expects, so the parser itself needs no changes.
+pub fn open_assembly(path: &Path) -> io::Result<Box<dyn BufRead>> {
+    let mut file = BufReader::new(File::open(path)?);
+    let magic = {
+        let buf = file.fill_buf()?;
+        let mut magic = [0u8; 4];
+        let n = buf.len().min(magic.len());
+        magic[..n].copy_from_slice(&buf[..n]);
+        magic
+    };
+
+    if magic[..2] == GZIP_MAGIC {
+        return Ok(Box::new(BufReader::new(GzDecoder::new(file))));
+    }
+    if magic == ZSTD_MAGIC {
+        return Ok(Box::new(BufReader::new(zstd::stream::Decoder::new(file)?)));
+    }
+
+    match path.extension().and_then(std::ffi::OsStr::to_str) {
+        Some("gz") => Ok(Box::new(BufReader::new(GzDecoder::new(file)))),
+        Some("zst") => Ok(Box::new(BufReader::new(zstd::stream::Decoder::new(file)?))),
+        _ => Ok(Box::new(file)),
+    }
+}
+
+/// Writes `contents` to `out`, treating a broken pipe (e.g. output piped
+/// into `head` or `less` and the reader exits early) as success rather
+/// than an error, so the tool exits zero instead of panicking mid-stream.
+pub fn write_ignoring_broken_pipe(out: &mut impl Write, contents: &str) -> io::Result<()> {
+    match out.write_all(contents.as_bytes()) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+#[test]
+fn test_write_ignoring_broken_pipe_suppresses_only_broken_pipe() {
+    struct BrokenPipeWriter;
+    impl Write for BrokenPipeWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let _ = buf;
+            Err(io::Error::from(io::ErrorKind::BrokenPipe))
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    struct OtherErrorWriter;
+    impl Write for OtherErrorWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let _ = buf;
+            Err(io::Error::from(io::ErrorKind::Other))
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    assert!(write_ignoring_broken_pipe(&mut BrokenPipeWriter, "asm").is_ok());
+    assert!(write_ignoring_broken_pipe(&mut OtherErrorWriter, "asm").is_err());
+}
+
+#[test]
+fn test_open_assembly_detects_gzip_by_magic_bytes_regardless_of_extension() {
+    use std::io::Read;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    let dir = std::env::temp_dir().join(format!(
+        "cargo-show-asm-decompress-test-{:?}",
+        std::thread::current().id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    // Deliberately not named `.gz`: detection must rely on the magic bytes.
+    let path = dir.join("weird_name.s");
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(b"\tretq\n").unwrap();
+    std::fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+    let mut reader = open_assembly(&path).unwrap();
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents).unwrap();
+    assert_eq!(contents, "\tretq\n");
+
+    std::fs::remove_dir_all(&dir).ok();
+}